@@ -0,0 +1,103 @@
+use std::path::PathBuf;
+
+use clap::{Parser, ValueEnum};
+
+/// A backend artifact `--emit` can be asked to produce. `Link` is the odd one out:
+/// it doesn't name a file `Codegen` writes, it names the final clang invocation
+/// that consumes the `.o` files all the other kinds build on top of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EmitKind {
+    #[value(name = "llvm-ir")]
+    LlvmIr,
+    Asm,
+    Obj,
+    Link,
+}
+
+/// Command line arguments accepted by the `lightc` driver.
+#[derive(Debug, Parser)]
+#[command(name = "lightc", about = "The light programming language compiler")]
+pub struct CliArgs {
+    /// Source file(s) to compile. Each is compiled to its own object in the build
+    /// dir; all of them (plus `--link`) are passed to the final link step. Not
+    /// required when `--test` is given
+    #[arg(required_unless_present = "test")]
+    pub files: Vec<PathBuf>,
+
+    /// Print the token stream produced by the lexer
+    #[arg(long)]
+    pub show_tokens: bool,
+
+    /// Print the AST produced by the parser
+    #[arg(long)]
+    pub show_ast: bool,
+
+    /// Print the AST after type checking
+    #[arg(long)]
+    pub show_typed_ast: bool,
+
+    /// Print the HIR produced by the lowering pass
+    #[arg(long)]
+    pub show_hir: bool,
+
+    /// Stop after codegen and emit an object file instead of linking
+    #[arg(long)]
+    pub compile_only: bool,
+
+    /// Name of the final linked executable (defaults to `a.out`)
+    #[arg(short, long)]
+    pub output: Option<String>,
+
+    /// Interpret the program directly instead of compiling it to native code
+    #[arg(long)]
+    pub run: bool,
+
+    /// Dump the post-typecheck IR (see the `ir` crate) to this path instead of
+    /// continuing on to codegen
+    #[arg(long)]
+    pub emit_ir: Option<PathBuf>,
+
+    /// Backend artifacts to build, comma-separated (`llvm-ir`, `asm`, `obj`,
+    /// `link`). Each of `llvm-ir`/`asm`/`obj` writes `<module>.ll`/`.s`/`.o` next
+    /// to the final binary; `link` runs the clang step. Defaults to building and
+    /// linking an executable, same as omitting the flag entirely today
+    #[arg(long, value_delimiter = ',', default_value = "link")]
+    pub emit: Vec<EmitKind>,
+
+    /// Bypass the `~/.cache/lightc` compilation cache and always run the full
+    /// pipeline (same effect as setting `LIGHTC_NO_CACHE`)
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Extra precompiled object/library to pass to the final link step, in
+    /// addition to the objects built from `files`. May be repeated
+    #[arg(long = "link")]
+    pub link: Vec<PathBuf>,
+
+    /// Run the golden-file test suite in this directory instead of compiling
+    /// `files`: walks it for `.lt` tests, compiles and runs each in isolation, and
+    /// checks the result against its `//~` directives
+    #[arg(long)]
+    pub test: Option<PathBuf>,
+
+    /// Rebuild and relink even if the up-to-date check says the existing object
+    /// or executable is newer than its inputs
+    #[arg(short = 'B', long)]
+    pub force: bool,
+
+    /// Target triple to compile for (e.g. `x86_64-unknown-linux-gnu`). Defaults
+    /// to `Codegen`'s host triple when unset
+    #[arg(long)]
+    pub target: Option<String>,
+
+    /// Program invoked for the final link step
+    #[arg(long, default_value = "clang")]
+    pub linker: String,
+
+    /// Path to the runtime object linked into every executable. Defaults to
+    /// `stdlib/stdlib.o` resolved against the current directory; override when
+    /// running from outside the repo root or targeting a different triple's
+    /// runtime
+    #[arg(long)]
+    pub stdlib: Option<PathBuf>,
+}
@@ -1,6 +1,6 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-pub use cli_args::CliArgs;
+pub use cli_args::{CliArgs, EmitKind};
 pub use literal::Literal;
 pub use prototype::Prototype;
 pub use symbol_table::{Symbol, SymbolTable};
@@ -13,7 +13,7 @@ pub mod symbol_table;
 
 // A Operator is an extra layer of abstraction between TokenType::Op() and the
 // actual character. Convenient in Rust to help constrain matching.
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum Operator {
     Add,
     AddEq,
@@ -76,7 +76,7 @@ impl std::fmt::Display for Operator {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Serialize)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Type {
     Int8,
     Int16,
@@ -93,6 +93,7 @@ pub enum Type {
     Void,
     Array(Box<Type>, usize),
     Comp(String),
+    Enum(String),
 }
 
 impl Type {
@@ -119,6 +120,17 @@ impl Type {
         }
     }
 
+    // Like `resolve_type()`, but treats `ty` as a declared `enum` (rather than a
+    // `Comp` struct reference) when it shows up in `known_enums`. Enum names live in
+    // their own namespace in the symbol table, so callers that know which names were
+    // declared with `enum` (e.g. the parser) pass them in here.
+    pub fn resolve_type_with_enums(ty: &str, known_enums: &[String]) -> Self {
+        if known_enums.iter().any(|e| e == ty) {
+            return Type::Enum(ty.to_owned());
+        }
+        Self::resolve_type(ty)
+    }
+
     pub fn as_strings() -> Vec<String> {
         vec![
             String::from("int8"),
@@ -137,6 +149,14 @@ impl Type {
             String::from("array"), // TODO: remove this when arrays are gone
         ]
     }
+
+    // `as_strings()`, extended with every declared `enum` name so the parser
+    // recognizes them as valid type annotations too.
+    pub fn as_strings_with_enums(known_enums: &[String]) -> Vec<String> {
+        let mut strings = Self::as_strings();
+        strings.extend(known_enums.iter().cloned());
+        strings
+    }
 }
 
 impl Default for Type {
@@ -155,6 +175,7 @@ impl std::fmt::Display for Type {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
             Type::Comp(ty) => format!("{}", ty),
+            Type::Enum(ty) => format!("{}", ty),
             _ => format!("{:?}", self).to_ascii_lowercase(),
         };
         write!(f, "{}", s)
@@ -0,0 +1,27 @@
+use crate::Value;
+
+/// Calls an `extern` function by name with already-evaluated arguments. This is a
+/// small hand-written table rather than real FFI, covering just enough of libc to
+/// make `run`-mode programs useful without linking against `stdlib.o`.
+pub fn call(name: &str, args: &[Value]) -> Result<Value, String> {
+    match name {
+        "printf" => printf(args),
+        "putchar" => {
+            let v = args.first().ok_or("putchar() expects 1 argument")?;
+            print!("{}", v.as_f64() as u8 as char);
+            Ok(Value::Int32(0))
+        },
+        _ => Err(format!("`{}` is not a known builtin for interpreted execution", name)),
+    }
+}
+
+fn printf(args: &[Value]) -> Result<Value, String> {
+    for (i, arg) in args.iter().enumerate() {
+        if i > 0 {
+            print!(" ");
+        }
+        print!("{}", arg);
+    }
+    println!();
+    Ok(Value::Int32(0))
+}
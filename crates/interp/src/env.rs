@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+
+use crate::Value;
+
+/// A stack of lexical scopes mapping names to runtime `Value`s. Mirrors the scope
+/// handling in `SymbolTable`, but stores values instead of type information.
+#[derive(Debug, Default)]
+pub struct Env {
+    scopes: Vec<HashMap<String, Value>>,
+}
+
+impl Env {
+    pub fn new() -> Self {
+        Env { scopes: vec![HashMap::new()] }
+    }
+
+    pub fn enter_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    pub fn leave_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    pub fn insert(&mut self, name: &str, value: Value) {
+        self.scopes.last_mut().expect("no active scope").insert(name.to_owned(), value);
+    }
+
+    /// Walks the scope stack from innermost to outermost, like `SymbolTable::get()`.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    /// Updates an existing binding in place, searching from innermost to outermost.
+    pub fn set(&mut self, name: &str, value: Value) -> Result<(), String> {
+        for scope in self.scopes.iter_mut().rev() {
+            if let Some(slot) = scope.get_mut(name) {
+                *slot = value;
+                return Ok(());
+            }
+        }
+        Err(format!("Assignment to unknown variable: `{}`", name))
+    }
+}
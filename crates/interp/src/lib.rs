@@ -0,0 +1,357 @@
+use std::collections::HashMap;
+
+use ast::{Ast, AstNode, AstVisitor, Literal, Prototype, Visitable};
+use common::Operator;
+use symbol_table::{Symbol, SymbolTable};
+
+pub use env::Env;
+pub use value::Value;
+
+mod builtins;
+mod env;
+mod value;
+
+/// Walks a typed AST and executes it directly, without going through LLVM. Gives a
+/// fast edit-run loop and a reference semantics to check compiled output against.
+pub struct Interp<'a> {
+    symbol_table: &'a SymbolTable<Symbol>,
+    env: Env,
+    functions: HashMap<String, (Prototype, Option<AstNode>)>,
+}
+
+impl<'a> Interp<'a> {
+    pub fn new(symbol_table: &'a SymbolTable<Symbol>) -> Self {
+        Interp { symbol_table, env: Env::new(), functions: HashMap::new() }
+    }
+
+    /// Registers every function in the program and then evaluates `main()`.
+    pub fn run(mut self, ast: Ast<AstNode>) -> Result<Value, String> {
+        for node in ast.nodes() {
+            if let Some((proto, body)) = node.as_fn() {
+                self.functions.insert(proto.name().to_owned(), (proto.clone(), body.cloned()));
+            }
+        }
+
+        let (proto, body) = self.functions.get("main").cloned().ok_or("No `main()` function found")?;
+        let body = body.ok_or("`main()` can't be an extern")?;
+        let _ = proto;
+        self.visit_node(body)
+    }
+
+    fn call_fn(&mut self, name: &str, args: Vec<Value>) -> Result<Value, String> {
+        if let Some((proto, Some(body))) = self.functions.get(name).cloned() {
+            self.env.enter_scope();
+            for (arg_val, (arg_name, _)) in args.into_iter().zip(proto.args()) {
+                self.env.insert(arg_name, arg_val);
+            }
+            let result = self.visit_node(body);
+            self.env.leave_scope();
+            return result;
+        }
+
+        let sym = self.symbol_table.get(name).ok_or_else(|| format!("Call to undefined function: `{}`", name))?;
+        if sym.is_extern() {
+            builtins::call(name, &args)
+        } else {
+            Err(format!("`{}` is declared but has no interpretable body", name))
+        }
+    }
+}
+
+impl<'a> AstVisitor for Interp<'a> {
+    type Node = AstNode;
+    type Result = Result<Value, String>;
+
+    fn visit_node(&mut self, node: Self::Node) -> Self::Result {
+        node.accept(self)
+    }
+
+    fn visit_for(&mut self, stmt: ast::For<Self::Node>) -> Self::Result {
+        self.env.enter_scope();
+        let start = match stmt.start_expr {
+            Some(expr) => self.visit_node(*expr)?,
+            None => Value::default_for(&stmt.start_antn, self.symbol_table),
+        };
+        self.env.insert(&stmt.start_name, start);
+
+        while self.visit_node((*stmt.cond_expr).clone())?.is_truthy() {
+            self.visit_node((*stmt.body).clone())?;
+            let stepped = self.visit_node((*stmt.step_expr).clone())?;
+            self.env.set(&stmt.start_name, stepped)?;
+        }
+
+        self.env.leave_scope();
+        Ok(Value::Void)
+    }
+
+    fn visit_let(&mut self, stmt: ast::Let<Self::Node>) -> Self::Result {
+        let value = match stmt.init {
+            Some(init) => self.visit_node(*init)?,
+            None => Value::default_for(&stmt.antn, self.symbol_table),
+        };
+        self.env.insert(&stmt.name, value);
+        Ok(Value::Void)
+    }
+
+    fn visit_fn(&mut self, _stmt: ast::Fn<Self::Node>) -> Self::Result {
+        // Functions are hoisted into `self.functions` up front by `run()`; nothing to
+        // evaluate when one is visited as a top-level node.
+        Ok(Value::Void)
+    }
+
+    fn visit_struct(&mut self, _stmt: ast::Struct<Self::Node>) -> Self::Result {
+        Ok(Value::Void)
+    }
+
+    fn visit_lit(&mut self, expr: ast::Lit<Self::Node>) -> Self::Result {
+        Ok(match expr.value {
+            Literal::Int8(v) => Value::Int8(v),
+            Literal::Int16(v) => Value::Int16(v),
+            Literal::Int32(v) => Value::Int32(v),
+            Literal::Int64(v) => Value::Int64(v),
+            Literal::UInt8(v) => Value::UInt8(v),
+            Literal::UInt16(v) => Value::UInt16(v),
+            Literal::UInt32(v) => Value::UInt32(v),
+            Literal::UInt64(v) => Value::UInt64(v),
+            Literal::Float(v) => Value::Float(v),
+            Literal::Double(v) => Value::Double(v),
+            Literal::Bool(v) => Value::Bool(v),
+            Literal::Char(v) => Value::Char(v),
+            Literal::Array { elements, .. } => {
+                let mut vals = Vec::with_capacity(elements.len());
+                for el in elements {
+                    vals.push(self.visit_node(el)?);
+                }
+                Value::Array(vals)
+            },
+        })
+    }
+
+    fn visit_ident(&mut self, expr: ast::Ident) -> Self::Result {
+        self.env.get(&expr.name).cloned().ok_or_else(|| format!("Unknown variable: `{}`", expr.name))
+    }
+
+    fn visit_binop(&mut self, expr: ast::BinOp<Self::Node>) -> Self::Result {
+        use Operator::*;
+
+        // Assignment and its compound forms need an lvalue, so they're handled
+        // before the generic "evaluate both sides" path below.
+        if let Assign | AddEq | SubEq | MulEq | DivEq = expr.op {
+            let name = match &*expr.lhs {
+                AstNode { kind: ast::node::Kind::Ident(ast::Ident { name }) } => name.clone(),
+                _ => return Err("Expected LHS to be a variable for assignment".to_string()),
+            };
+            let rhs = self.visit_node(*expr.rhs)?;
+            let new_val = match expr.op {
+                Assign => rhs,
+                AddEq => arith(Add, self.env.get(&name).cloned().unwrap(), rhs)?,
+                SubEq => arith(Sub, self.env.get(&name).cloned().unwrap(), rhs)?,
+                MulEq => arith(Mul, self.env.get(&name).cloned().unwrap(), rhs)?,
+                DivEq => arith(Div, self.env.get(&name).cloned().unwrap(), rhs)?,
+                _ => unreachable!(),
+            };
+            self.env.set(&name, new_val.clone())?;
+            return Ok(new_val);
+        }
+
+        let lhs = self.visit_node(*expr.lhs)?;
+        let rhs = self.visit_node(*expr.rhs)?;
+        arith(expr.op, lhs, rhs)
+    }
+
+    fn visit_unop(&mut self, expr: ast::UnOp<Self::Node>) -> Self::Result {
+        use Operator::*;
+
+        if let Inc | Dec = expr.op {
+            let name = match &*expr.rhs {
+                AstNode { kind: ast::node::Kind::Ident(ast::Ident { name }) } => name.clone(),
+                _ => return Err(format!("Expected a variable as the operand of `{}`", expr.op)),
+            };
+            let cur = self.env.get(&name).cloned().ok_or_else(|| format!("Unknown variable: `{}`", name))?;
+            let one = Value::Int32(1);
+            let updated = arith(if expr.op == Inc { Add } else { Sub }, cur, one)?;
+            self.env.set(&name, updated.clone())?;
+            return Ok(updated);
+        }
+
+        let rhs = self.visit_node(*expr.rhs)?;
+        match expr.op {
+            Sub => negate(rhs),
+            Not => Ok(Value::Bool(!rhs.is_truthy())),
+            op => Err(format!("`{}` is not a valid unary operator", op)),
+        }
+    }
+
+    fn visit_call(&mut self, expr: ast::Call<Self::Node>) -> Self::Result {
+        let mut args = Vec::with_capacity(expr.args.len());
+        for arg in expr.args {
+            args.push(self.visit_node(arg)?);
+        }
+        self.call_fn(&expr.name, args)
+    }
+
+    fn visit_cond(&mut self, expr: ast::Cond<Self::Node>) -> Self::Result {
+        if self.visit_node(*expr.cond_expr)?.is_truthy() {
+            self.visit_node(*expr.then_block)
+        } else if let Some(else_block) = expr.else_block {
+            self.visit_node(*else_block)
+        } else {
+            Ok(Value::Void)
+        }
+    }
+
+    fn visit_block(&mut self, expr: ast::Block<Self::Node>) -> Self::Result {
+        self.env.enter_scope();
+        let mut last = Value::Void;
+        for node in expr.list {
+            last = self.visit_node(node)?;
+        }
+        self.env.leave_scope();
+        Ok(last)
+    }
+
+    fn visit_index(&mut self, expr: ast::Index<Self::Node>) -> Self::Result {
+        let binding = self.visit_node(*expr.binding)?;
+        let idx = self.visit_node(*expr.idx)?;
+        let els = match binding {
+            Value::Array(els) => els,
+            _ => return Err("Can't index a non-array value".to_string()),
+        };
+        let idx = idx.as_f64() as usize;
+        els.get(idx).cloned().ok_or_else(|| format!("Index `{}` out of bounds", idx))
+    }
+}
+
+/// Dispatches every `Operator` variant that isn't assignment-related, applying the
+/// arithmetic/comparison/logical semantics with wrapping integer width.
+fn arith(op: Operator, lhs: Value, rhs: Value) -> Result<Value, String> {
+    use Operator::*;
+
+    macro_rules! int_op {
+        ($lhs:expr, $rhs:expr, $wrapping:ident) => {
+            match ($lhs, $rhs) {
+                (Value::Int8(a), Value::Int8(b)) => Value::Int8(a.$wrapping(b)),
+                (Value::Int16(a), Value::Int16(b)) => Value::Int16(a.$wrapping(b)),
+                (Value::Int32(a), Value::Int32(b)) => Value::Int32(a.$wrapping(b)),
+                (Value::Int64(a), Value::Int64(b)) => Value::Int64(a.$wrapping(b)),
+                (Value::UInt8(a), Value::UInt8(b)) => Value::UInt8(a.$wrapping(b)),
+                (Value::UInt16(a), Value::UInt16(b)) => Value::UInt16(a.$wrapping(b)),
+                (Value::UInt32(a), Value::UInt32(b)) => Value::UInt32(a.$wrapping(b)),
+                (Value::UInt64(a), Value::UInt64(b)) => Value::UInt64(a.$wrapping(b)),
+                (Value::Float(a), Value::Float(b)) => Value::Float(float_op!(a, b, $wrapping)),
+                (Value::Double(a), Value::Double(b)) => Value::Double(float_op!(a, b, $wrapping)),
+                _ => return Err(format!("`{}` isn't defined for these operand types", op)),
+            }
+        };
+    }
+
+    macro_rules! float_op {
+        ($a:expr, $b:expr, wrapping_add) => {
+            $a + $b
+        };
+        ($a:expr, $b:expr, wrapping_sub) => {
+            $a - $b
+        };
+        ($a:expr, $b:expr, wrapping_mul) => {
+            $a * $b
+        };
+    }
+
+    Ok(match op {
+        Add => int_op!(lhs, rhs, wrapping_add),
+        Sub => int_op!(lhs, rhs, wrapping_sub),
+        Mul => int_op!(lhs, rhs, wrapping_mul),
+        Div => div(lhs, rhs)?,
+        Pow => pow(lhs, rhs),
+        BitAnd => bitop(lhs, rhs, |a, b| a & b)?,
+        BitOr => bitop(lhs, rhs, |a, b| a | b)?,
+        BitXor => bitop(lhs, rhs, |a, b| a ^ b)?,
+        And => Value::Bool(lhs.is_truthy() && rhs.is_truthy()),
+        Or => Value::Bool(lhs.is_truthy() || rhs.is_truthy()),
+        Eq => Value::Bool(lhs == rhs),
+        NotEq => Value::Bool(lhs != rhs),
+        Gt => Value::Bool(lhs.as_f64() > rhs.as_f64()),
+        GtEq => Value::Bool(lhs.as_f64() >= rhs.as_f64()),
+        Lt => Value::Bool(lhs.as_f64() < rhs.as_f64()),
+        LtEq => Value::Bool(lhs.as_f64() <= rhs.as_f64()),
+        op => return Err(format!("`{}` can't be evaluated as a binary operator", op)),
+    })
+}
+
+// Integer division by zero stays a hard error (there's no wrapping result to
+// fall back on); float division by zero instead follows IEEE-754 and produces
+// `inf`/`NaN`, same as the `fdiv` instruction the compiled binary executes, so
+// this reference semantics doesn't disagree with it for the same program.
+fn div(lhs: Value, rhs: Value) -> Result<Value, String> {
+    macro_rules! int_div {
+        ($a:expr, $b:expr) => {
+            if $b == 0 {
+                return Err("Division by zero".to_string());
+            } else {
+                $a.wrapping_div($b)
+            }
+        };
+    }
+
+    Ok(match (lhs, rhs) {
+        (Value::Int8(a), Value::Int8(b)) => Value::Int8(int_div!(a, b)),
+        (Value::Int16(a), Value::Int16(b)) => Value::Int16(int_div!(a, b)),
+        (Value::Int32(a), Value::Int32(b)) => Value::Int32(int_div!(a, b)),
+        (Value::Int64(a), Value::Int64(b)) => Value::Int64(int_div!(a, b)),
+        (Value::UInt8(a), Value::UInt8(b)) => Value::UInt8(int_div!(a, b)),
+        (Value::UInt16(a), Value::UInt16(b)) => Value::UInt16(int_div!(a, b)),
+        (Value::UInt32(a), Value::UInt32(b)) => Value::UInt32(int_div!(a, b)),
+        (Value::UInt64(a), Value::UInt64(b)) => Value::UInt64(int_div!(a, b)),
+        (Value::Float(a), Value::Float(b)) => Value::Float(a / b),
+        (Value::Double(a), Value::Double(b)) => Value::Double(a / b),
+        (a, _) => return Err(format!("`/` isn't defined for `{}`", a)),
+    })
+}
+
+fn pow(lhs: Value, rhs: Value) -> Value {
+    match lhs {
+        Value::Float(a) => Value::Float(a.powf(rhs.as_f64() as f32)),
+        Value::Double(a) => Value::Double(a.powf(rhs.as_f64())),
+        other => {
+            let base = other.as_f64();
+            let result = base.powf(rhs.as_f64());
+            match other {
+                Value::Int8(_) => Value::Int8(result as i8),
+                Value::Int16(_) => Value::Int16(result as i16),
+                Value::Int32(_) => Value::Int32(result as i32),
+                Value::Int64(_) => Value::Int64(result as i64),
+                Value::UInt8(_) => Value::UInt8(result as u8),
+                Value::UInt16(_) => Value::UInt16(result as u16),
+                Value::UInt32(_) => Value::UInt32(result as u32),
+                Value::UInt64(_) => Value::UInt64(result as u64),
+                _ => unreachable!("non-numeric base in `**`"),
+            }
+        },
+    }
+}
+
+fn bitop(lhs: Value, rhs: Value, f: impl Fn(u64, u64) -> u64) -> Result<Value, String> {
+    Ok(match (&lhs, &rhs) {
+        (Value::Int8(a), Value::Int8(b)) => Value::Int8(f(*a as u64, *b as u64) as i8),
+        (Value::Int16(a), Value::Int16(b)) => Value::Int16(f(*a as u64, *b as u64) as i16),
+        (Value::Int32(a), Value::Int32(b)) => Value::Int32(f(*a as u64, *b as u64) as i32),
+        (Value::Int64(a), Value::Int64(b)) => Value::Int64(f(*a as u64, *b as u64) as i64),
+        (Value::UInt8(a), Value::UInt8(b)) => Value::UInt8(f(*a as u64, *b as u64) as u8),
+        (Value::UInt16(a), Value::UInt16(b)) => Value::UInt16(f(*a as u64, *b as u64) as u16),
+        (Value::UInt32(a), Value::UInt32(b)) => Value::UInt32(f(*a as u64, *b as u64) as u32),
+        (Value::UInt64(a), Value::UInt64(b)) => Value::UInt64(f(*a, *b)),
+        _ => return Err("bitwise operators require integer operands".to_string()),
+    })
+}
+
+fn negate(v: Value) -> Result<Value, String> {
+    Ok(match v {
+        Value::Int8(a) => Value::Int8(-a),
+        Value::Int16(a) => Value::Int16(-a),
+        Value::Int32(a) => Value::Int32(-a),
+        Value::Int64(a) => Value::Int64(-a),
+        Value::Float(a) => Value::Float(-a),
+        Value::Double(a) => Value::Double(-a),
+        other => return Err(format!("`-` isn't defined for `{}`", other)),
+    })
+}
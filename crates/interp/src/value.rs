@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use common::Type;
+use symbol_table::{AssocData, Symbol, SymbolTable};
+
+/// A runtime value produced by the tree-walking interpreter. Mirrors the shape of
+/// `common::Type` so every typed AST node has an obvious `Value` to evaluate to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    UInt8(u8),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    Float(f32),
+    Double(f64),
+    Bool(bool),
+    Char(u8),
+    Void,
+    Array(Vec<Value>),
+    Comp(HashMap<String, Value>),
+    /// An enum value: the active variant's name, plus its payload values (`None`
+    /// for a unit variant).
+    Variant(String, Option<Vec<Value>>),
+}
+
+impl Value {
+    /// Constructs the default (zero) value for a given `Type`, used to initialize
+    /// uninitialized `let` bindings the same way the type checker does. A `Comp`
+    /// struct recurses into each field's own default, looked up from `symbol_table`,
+    /// rather than handing back an empty map a later field access would panic on.
+    pub fn default_for(ty: &Type, symbol_table: &SymbolTable<Symbol>) -> Self {
+        match ty {
+            Type::Int8 => Value::Int8(0),
+            Type::Int16 => Value::Int16(0),
+            Type::Int32 => Value::Int32(0),
+            Type::Int64 => Value::Int64(0),
+            Type::UInt8 => Value::UInt8(0),
+            Type::UInt16 => Value::UInt16(0),
+            Type::UInt32 => Value::UInt32(0),
+            Type::UInt64 => Value::UInt64(0),
+            Type::Float => Value::Float(0.0),
+            Type::Double => Value::Double(0.0),
+            Type::Bool => Value::Bool(false),
+            Type::Char => Value::Char(0),
+            Type::Void => Value::Void,
+            Type::Array(inner, len) => Value::Array(vec![Value::default_for(inner, symbol_table); *len]),
+            Type::Comp(name) => {
+                let fields = symbol_table
+                    .get(&format!("_type_{}", name))
+                    .and_then(|sym| match &sym.data {
+                        AssocData::Struct(s) => Some(s.fields.clone()),
+                        _ => None,
+                    })
+                    .unwrap_or_default();
+                let values = fields
+                    .into_iter()
+                    .map(|(field_name, field_ty)| {
+                        let field_ty = Type::resolve_type(&field_ty);
+                        let value = Value::default_for(&field_ty, symbol_table);
+                        (field_name, value)
+                    })
+                    .collect::<HashMap<_, _>>();
+                Value::Comp(values)
+            },
+            Type::Enum(name) => {
+                let variants = symbol_table
+                    .get(&format!("_type_{}", name))
+                    .and_then(|sym| match &sym.data {
+                        AssocData::Enum(e) => Some(e.variants.clone()),
+                        _ => None,
+                    })
+                    .unwrap_or_default();
+                // Defaults to the first declared variant, same as the interpreter
+                // already defaults a `Comp`'s missing fields to their own defaults
+                // rather than refusing to produce a value at all.
+                let (variant_name, payload_tys) = variants.into_iter().next().unwrap_or_default();
+                let payload = payload_tys.map(|tys| {
+                    tys.iter()
+                        .map(|ty| Value::default_for(&Type::resolve_type(ty), symbol_table))
+                        .collect()
+                });
+                Value::Variant(variant_name, payload)
+            },
+        }
+    }
+
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Bool(b) => *b,
+            _ => unreachable!("expected bool value in a boolean context"),
+        }
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            Value::Int8(v) => *v as f64,
+            Value::Int16(v) => *v as f64,
+            Value::Int32(v) => *v as f64,
+            Value::Int64(v) => *v as f64,
+            Value::UInt8(v) => *v as f64,
+            Value::UInt16(v) => *v as f64,
+            Value::UInt32(v) => *v as f64,
+            Value::UInt64(v) => *v as f64,
+            Value::Float(v) => *v as f64,
+            Value::Double(v) => *v,
+            Value::Char(v) => *v as f64,
+            _ => unreachable!("expected numeric value"),
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Int8(v) => write!(f, "{}", v),
+            Value::Int16(v) => write!(f, "{}", v),
+            Value::Int32(v) => write!(f, "{}", v),
+            Value::Int64(v) => write!(f, "{}", v),
+            Value::UInt8(v) => write!(f, "{}", v),
+            Value::UInt16(v) => write!(f, "{}", v),
+            Value::UInt32(v) => write!(f, "{}", v),
+            Value::UInt64(v) => write!(f, "{}", v),
+            Value::Float(v) => write!(f, "{}", v),
+            Value::Double(v) => write!(f, "{}", v),
+            Value::Bool(v) => write!(f, "{}", v),
+            Value::Char(v) => write!(f, "{}", *v as char),
+            Value::Void => write!(f, "void"),
+            Value::Array(els) => {
+                write!(f, "[")?;
+                for (i, el) in els.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", el)?;
+                }
+                write!(f, "]")
+            },
+            Value::Comp(fields) => {
+                write!(f, "{{")?;
+                for (i, (name, val)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", name, val)?;
+                }
+                write!(f, "}}")
+            },
+            Value::Variant(name, payload) => match payload {
+                Some(vals) => {
+                    write!(f, "{}(", name)?;
+                    for (i, val) in vals.iter().enumerate() {
+                        if i > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{}", val)?;
+                    }
+                    write!(f, ")")
+                },
+                None => write!(f, "{}", name),
+            },
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use ast::{Ast, AstNode};
+use symbol_table::{Symbol, SymbolTable};
+
+pub use reader::{parse, IrError};
+pub use writer::dump;
+
+mod reader;
+mod writer;
+
+// A single top-level declaration in the textual IR: the `Symbol` the frontend
+// recorded for it (already carrying fully-resolved types, `is_extern`, etc.) plus
+// the typed body, if any. Struct and enum declarations have no body to speak of,
+// so `node` is only populated for functions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IrItem {
+    pub symbol: Symbol,
+    pub node: Option<AstNode>,
+}
+
+// Rebuilds an `Ast<AstNode>` (functions only; struct/enum declarations live
+// entirely in the returned `SymbolTable`) and a `SymbolTable` holding each
+// item's symbol, from a flat list of `IrItem`s. Only the dumped
+// function/struct/enum symbols come back; enum variant symbols are not part of
+// the dump and are not restored. Errors rather than silently
+// shadowing if two items' symbols conflict (see `chunk0-2`'s
+// `Symbolic::conflicts_with()`).
+pub fn into_parts(items: Vec<IrItem>) -> Result<(Ast<AstNode>, SymbolTable<Symbol>), String> {
+    let mut ast = Ast::new();
+    let mut symbol_table = SymbolTable::new();
+    for item in items {
+        symbol_table.insert(item.symbol)?;
+        if let Some(node) = item.node {
+            ast.add(node);
+        }
+    }
+    Ok((ast, symbol_table))
+}
@@ -0,0 +1,135 @@
+use ast::AstNode;
+use symbol_table::{Symbol, SymbolTable};
+
+use crate::IrItem;
+
+/// Reads the textual IR `dump()` produces back into a list of `IrItem`s.
+///
+/// Function bodies are re-lexed and re-parsed from their `Display`ed text wrapped
+/// back into a synthetic `fn` using the signature recovered from the directive's
+/// `Symbol`. This recovers the same shape of `AstNode` the original frontend built,
+/// but not the exact literal `Type`s the original typecheck pass assigned to
+/// un-annotated numeric literals in the body (those are re-inferred from scratch
+/// by the reparse, same as typechecking any other program); callers that need a
+/// byte-for-byte round trip should typecheck the reparsed body again rather than
+/// trusting it as already-checked.
+///
+/// Every directive's `Symbol` is inserted into one shared table before any body
+/// is reparsed, so a function whose signature or statements reference a
+/// struct/enum declared by a sibling directive in the same dump resolves against
+/// an already-known type instead of failing to reparse.
+pub fn parse(ir: &str) -> Result<Vec<IrItem>, IrError> {
+    let directives = ir
+        .split(";;end\n")
+        .map(str::trim)
+        .filter(|directive| !directive.is_empty())
+        .map(parse_header)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut symbol_table = SymbolTable::new();
+    for (_, _, symbol, _) in &directives {
+        symbol_table.insert(symbol.clone()).map_err(IrError::Conflict)?;
+    }
+
+    directives
+        .into_iter()
+        .map(|(kind, name, symbol, body_text)| build_item(kind, name, symbol, body_text, &symbol_table))
+        .collect()
+}
+
+fn parse_header(directive: &str) -> Result<(&str, &str, Symbol, String), IrError> {
+    let mut lines = directive.lines();
+
+    let header = lines.next().ok_or_else(|| IrError::MalformedDirective(directive.to_owned()))?;
+    let (kind, name) =
+        header.split_once(' ').ok_or_else(|| IrError::MalformedDirective(header.to_owned()))?;
+
+    let symbol_json = lines.next().ok_or_else(|| IrError::MalformedDirective(directive.to_owned()))?;
+    let symbol: Symbol =
+        serde_json::from_str(symbol_json).map_err(|e| IrError::InvalidSymbol(e.to_string()))?;
+
+    let body_text = lines.collect::<Vec<_>>().join("\n");
+    Ok((kind, name, symbol, body_text))
+}
+
+fn build_item(
+    kind: &str, name: &str, symbol: Symbol, body_text: String, symbol_table: &SymbolTable<Symbol>,
+) -> Result<IrItem, IrError> {
+    let node = match kind {
+        "fn" if !body_text.is_empty() => Some(reparse_fn_body(name, &symbol, &body_text, symbol_table)?),
+        "fn" | "struct" | "enum" => None,
+        other => return Err(IrError::MalformedDirective(format!("unknown directive kind `{}`", other))),
+    };
+
+    Ok(IrItem { symbol, node })
+}
+
+// Stitches the recovered signature and body text back into a standalone program
+// so the real `lex`/`parse` crates can hand back a proper `AstNode::Fn`. Reparses
+// against a clone of the dump's shared `symbol_table` (rather than a fresh empty
+// one) so a body referencing a struct/enum type declared by a sibling directive
+// resolves instead of being treated as an unknown type.
+fn reparse_fn_body(
+    name: &str, symbol: &Symbol, body_text: &str, symbol_table: &SymbolTable<Symbol>,
+) -> Result<AstNode, IrError> {
+    let args = symbol.args().iter().map(|(arg, ty)| format!("{}: {}", arg, ty)).collect::<Vec<_>>().join(", ");
+    let src = format!("fn {}({}) -> {} {{\n{}\n}}", name, args, symbol.ret_ty(), body_text);
+
+    let tokens = lex::Lex::new(&src).scan().map_err(|e| IrError::Reparse(e.to_string()))?;
+    let mut symbol_table = symbol_table.clone();
+    let ast = parse::Parse::new(&tokens, &mut symbol_table)
+        .parse()
+        .map_err(IrError::Reparse)?;
+
+    ast.into_nodes().into_iter().next().ok_or_else(|| IrError::Reparse(format!("empty reparse of `{}`", name)))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IrError {
+    MalformedDirective(String),
+    InvalidSymbol(String),
+    Reparse(String),
+    Conflict(String),
+}
+
+impl std::fmt::Display for IrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IrError::MalformedDirective(s) => write!(f, "malformed IR directive: {}", s),
+            IrError::InvalidSymbol(s) => write!(f, "invalid symbol metadata: {}", s),
+            IrError::Reparse(s) => write!(f, "error reparsing IR body: {}", s),
+            IrError::Conflict(s) => write!(f, "conflicting symbols in IR: {}", s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_fn_directive_reparses_its_body_against_its_own_symbol() {
+        let symbol = Symbol::new_fn("foo", &[], "i32", false);
+        let symbol_json = serde_json::to_string(&symbol).unwrap();
+        let ir = format!("fn foo\n{}\n0\n;;end\n", symbol_json);
+
+        let items = parse(&ir).unwrap();
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].symbol, symbol);
+        assert!(items[0].node.is_some());
+    }
+
+    #[test]
+    fn parse_struct_directive_has_no_body_node() {
+        let symbol = Symbol::new_ty("Foo");
+        let symbol_json = serde_json::to_string(&symbol).unwrap();
+        let ir = format!("struct Foo\n{}\n;;end\n", symbol_json);
+
+        let items = parse(&ir).unwrap();
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].symbol, symbol);
+        assert!(items[0].node.is_none());
+    }
+}
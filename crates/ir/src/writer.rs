@@ -0,0 +1,86 @@
+use std::fmt::Write as _;
+
+use ast::{Ast, AstNode};
+use symbol_table::{Symbol, SymbolTable};
+
+/// Serializes a post-typecheck `Ast` and the `SymbolTable` it was checked against
+/// into a stable textual IR, in the format shown below. One directive per
+/// function/struct/enum, in program order for functions and structs, followed by
+/// every enum the symbol table knows about (enums have no AST node of their own;
+/// see `chunk0-2`).
+///
+/// ```text
+/// fn add
+/// {"name":"add", ...}
+/// <typed body, via AstNode's Display>
+/// ;;end
+/// ```
+pub fn dump(ast: &Ast<AstNode>, symbol_table: &SymbolTable<Symbol>) -> String {
+    let mut out = String::new();
+
+    for node in ast.nodes() {
+        let (kind, name, body) = match node.as_fn() {
+            Some((proto, body)) => ("fn", proto.name().to_owned(), body),
+            None => match node.as_struct() {
+                Some(name) => ("struct", name.to_owned(), None),
+                None => continue,
+            },
+        };
+
+        // Structs (like enums, see the loop below) are keyed under `_type_<name>`,
+        // not the bare name — the same convention `Value::default_for()` and
+        // `Symbol::conflicts_with()` rely on.
+        let lookup_key = if kind == "struct" { format!("_type_{}", name) } else { name.clone() };
+        let symbol = symbol_table.get(&lookup_key).expect("every dumped item has a symbol table entry");
+        write_directive(&mut out, kind, &name, symbol, body);
+    }
+
+    for symbol in symbol_table.iter().filter(|s| s.is_enum()) {
+        let name = symbol.name.trim_start_matches("_type_");
+        write_directive(&mut out, "enum", name, symbol, None);
+    }
+
+    out
+}
+
+fn write_directive(out: &mut String, kind: &str, name: &str, symbol: &Symbol, body: Option<&AstNode>) {
+    writeln!(out, "{} {}", kind, name).unwrap();
+    writeln!(out, "{}", serde_json::to_string(symbol).expect("Symbol is always representable as JSON")).unwrap();
+    if let Some(body) = body {
+        writeln!(out, "{}", body).unwrap();
+    }
+    out.push_str(";;end\n\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use symbol_table::{AssocData, StructData};
+
+    use super::*;
+
+    #[test]
+    fn dump_struct_looks_up_its_symbol_under_the_type_namespace() {
+        let mut ast = Ast::new();
+        ast.add(AstNode::new_struct("Foo".to_owned(), vec![], vec![]));
+
+        let mut symbol_table = SymbolTable::new();
+        let symbol =
+            Symbol { name: "_type_Foo".to_owned(), data: AssocData::Struct(StructData { fields: vec![], methods: None }) };
+        symbol_table.insert(symbol.clone()).unwrap();
+
+        let dumped = dump(&ast, &symbol_table);
+        assert!(dumped.starts_with("struct Foo\n"));
+        assert!(dumped.contains(&serde_json::to_string(&symbol).unwrap()));
+    }
+
+    #[test]
+    fn dump_emits_a_directive_for_every_enum_in_the_symbol_table() {
+        let ast = Ast::new();
+
+        let mut symbol_table = SymbolTable::new();
+        symbol_table.insert(Symbol::new_enum("Color", &[("Red".to_owned(), None), ("Green".to_owned(), None)])).unwrap();
+
+        let dumped = dump(&ast, &symbol_table);
+        assert!(dumped.starts_with("enum Color\n"));
+    }
+}
@@ -0,0 +1,97 @@
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use common::CliArgs;
+use sha2::{Digest as _, Sha256};
+
+/// Baked into every digest so a `lightc` upgrade invalidates existing entries
+/// instead of handing back objects a different codegen produced.
+const COMPILER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Disables the cache outright, same spelling convention as `RUST_LOG`/`NO_COLOR`.
+/// Checked in addition to `--no-cache` so CI can blanket-disable it without
+/// touching every invocation site.
+const NO_CACHE_ENV: &str = "LIGHTC_NO_CACHE";
+
+/// An sccache-style, content-addressed store of compiled object files, keyed by a
+/// digest over everything that can change what codegen produces. A hit turns a
+/// `lightc foo.lt` invocation from a full Lex->Parse->Tych->Lower->Codegen pipeline
+/// into a file copy.
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    /// Returns `None` when the cache is disabled (`--no-cache` or
+    /// `LIGHTC_NO_CACHE`) or its directory can't be created; either way, the
+    /// caller should just run the full pipeline.
+    pub fn open(args: &CliArgs) -> Option<Self> {
+        if args.no_cache || env::var_os(NO_CACHE_ENV).is_some() {
+            return None;
+        }
+
+        let dir = cache_dir();
+        fs::create_dir_all(&dir).ok()?;
+        Some(Cache { dir })
+    }
+
+    /// Hashes every input that can change the produced object file: the
+    /// normalized source, the subset of `CliArgs` that affects codegen (not
+    /// `output`, which only names the final link target, and not `files`, whose
+    /// paths would otherwise collapse the hit rate), `stdlib.o`'s
+    /// bytes, and `COMPILER_VERSION`. A missing `stdlib.o` hashes as a fixed
+    /// sentinel rather than being silently treated the same as an empty file.
+    ///
+    /// `--target` is included because it changes the LLVM target machine
+    /// `Codegen` builds against (a host build and a cross build of the same
+    /// source produce different objects and must never share a cache entry);
+    /// its absence hashes as a fixed sentinel, same as a missing `stdlib.o`,
+    /// so "no `--target`" and `--target ""` can't collide.
+    pub fn digest(source: &str, args: &CliArgs, stdlib_path: &Path) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(normalize_source(source).as_bytes());
+        hasher.update(COMPILER_VERSION.as_bytes());
+        hasher.update([args.compile_only as u8]);
+        hasher.update(args.target.as_deref().unwrap_or("<host target>").as_bytes());
+        match fs::read(stdlib_path) {
+            Ok(bytes) => hasher.update(bytes),
+            Err(_) => hasher.update(b"<missing stdlib.o>"),
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Returns the cached object's path if `digest` has an entry.
+    pub fn get(&self, digest: &str) -> Option<PathBuf> {
+        let path = self.entry_path(digest);
+        path.exists().then_some(path)
+    }
+
+    /// Stores `module_file` under `digest`. Copies to a sibling temp file and
+    /// renames into place, so two `lightc` invocations racing on the same digest
+    /// never observe (or produce) a partially-written entry.
+    pub fn put(&self, digest: &str, module_file: &Path) -> std::io::Result<()> {
+        let tmp = self.dir.join(format!("{}.o.{}.tmp", digest, std::process::id()));
+        fs::copy(module_file, &tmp)?;
+        fs::rename(tmp, self.entry_path(digest))
+    }
+
+    fn entry_path(&self, digest: &str) -> PathBuf {
+        self.dir.join(format!("{}.o", digest))
+    }
+}
+
+// Line endings are the only source normalization worth doing here: everything
+// else that could make semantically-identical sources hash differently (ASTs,
+// whitespace-insensitive formatting) is exactly what the rest of the pipeline
+// already canonicalizes away, so hashing raw bytes past this is correct, not lazy.
+fn normalize_source(source: &str) -> String {
+    source.replace("\r\n", "\n")
+}
+
+fn cache_dir() -> PathBuf {
+    dirs_home().join(".cache").join("lightc")
+}
+
+fn dirs_home() -> PathBuf {
+    env::var_os("HOME").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."))
+}
@@ -0,0 +1,444 @@
+use std::ops::Range;
+
+use common::Type;
+
+/// A byte-offset range plus a human-friendly 1-indexed (line, col) for the start of
+/// a token, so parser/typecheck errors (and, eventually, an LSP) can point at an
+/// exact source location instead of just a token value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: u32,
+    pub col: u32,
+}
+
+/// A `Token` (or anything else) paired with the `Span` it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub span: Span,
+}
+
+/// A parsed numeric literal: the bits the lexer was able to recover, plus the
+/// `Type` it should be treated as downstream (either the declared suffix, or the
+/// language's default for an un-suffixed int/float literal).
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumTok {
+    pub value: NumValue,
+    pub ty: Type,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumValue {
+    Int(u64),
+    Float(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Extern,
+    Fn,
+    Let,
+    If,
+    Else,
+    Ident(String),
+    Num(NumTok),
+    OpenParen,
+    CloseParen,
+    OpenBrace,
+    CloseBrace,
+    Comma,
+    Assign,
+    Op(char),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LexErrorKind {
+    InvalidNum,
+    UnexpectedChar(char),
+}
+
+impl std::fmt::Display for LexErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LexErrorKind::InvalidNum => write!(f, "invalid numeric literal"),
+            LexErrorKind::UnexpectedChar(c) => write!(f, "unexpected character `{}`", c),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LexError {
+    pub kind: LexErrorKind,
+    pub span: Span,
+}
+
+impl std::fmt::Display for LexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {}:{}", self.kind, self.span.line, self.span.col)
+    }
+}
+
+/// Scans `lightc` source into a `Spanned<Token>` stream. Implements `Iterator` so
+/// callers can `.collect::<Result<Vec<_>, _>>()` the whole program, or pull tokens
+/// lazily.
+pub struct Lexer {
+    chars: Vec<char>,
+    pos: usize,
+    byte_pos: usize,
+    line: u32,
+    col: u32,
+}
+
+impl Lexer {
+    pub fn new(input: &str) -> Self {
+        Lexer { chars: input.chars().collect(), pos: 0, byte_pos: 0, line: 1, col: 1 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        self.byte_pos += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.col = 1;
+        } else {
+            self.col += 1;
+        }
+        Some(c)
+    }
+
+    // A resumable cursor position, captured before scanning a token and handed
+    // back to `span_from()` once the token is fully consumed.
+    fn mark(&self) -> (usize, u32, u32) {
+        (self.byte_pos, self.line, self.col)
+    }
+
+    fn span_from(&self, start: (usize, u32, u32)) -> Span {
+        Span { start: start.0, end: self.byte_pos, line: start.1, col: start.2 }
+    }
+
+    fn skip_trivia(&mut self) {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.advance();
+                },
+                Some('/') if self.peek_at(1) == Some('/') => {
+                    while let Some(c) = self.peek() {
+                        if c == '\n' {
+                            break;
+                        }
+                        self.advance();
+                    }
+                },
+                _ => break,
+            }
+        }
+    }
+
+    // Consumes a run of digits matching `is_digit`, skipping `_` separators. The
+    // result may be empty; callers decide whether that's meaningful.
+    fn consume_digit_run(&mut self, is_digit: impl Fn(char) -> bool) -> String {
+        let mut s = String::new();
+        while let Some(c) = self.peek() {
+            if is_digit(c) {
+                s.push(c);
+                self.advance();
+            } else if c == '_' {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        s
+    }
+
+    // Consumes a suffix like `i8`/`u64`/`f`/`d` immediately following a numeral,
+    // matching it against `table` exactly. Returns an error if a suffix-shaped run
+    // of letters/digits doesn't match any entry in `table`.
+    fn consume_suffix(&mut self, table: &[(&str, Type)]) -> Result<Option<Type>, LexErrorKind> {
+        if !matches!(self.peek(), Some(c) if c.is_ascii_alphabetic()) {
+            return Ok(None);
+        }
+
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_ascii_alphanumeric()) {
+            self.advance();
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+
+        table
+            .iter()
+            .find(|(name, _)| *name == text)
+            .map(|(_, ty)| Some(ty.clone()))
+            .ok_or(LexErrorKind::InvalidNum)
+    }
+
+    fn scan_number(&mut self) -> Result<Token, LexErrorKind> {
+        const INT_SUFFIXES: &[(&str, Type)] = &[
+            ("i8", Type::Int8),
+            ("i16", Type::Int16),
+            ("i32", Type::Int32),
+            ("i64", Type::Int64),
+            ("u8", Type::UInt8),
+            ("u16", Type::UInt16),
+            ("u32", Type::UInt32),
+            ("u64", Type::UInt64),
+        ];
+        const FLOAT_SUFFIXES: &[(&str, Type)] = &[("f", Type::Float), ("d", Type::Double)];
+
+        if self.peek() == Some('0') && matches!(self.peek_at(1), Some('x') | Some('X')) {
+            self.advance();
+            self.advance();
+            return self.scan_hex_number(INT_SUFFIXES, FLOAT_SUFFIXES);
+        }
+        if self.peek() == Some('0') && matches!(self.peek_at(1), Some('o') | Some('O')) {
+            self.advance();
+            self.advance();
+            let digits = self.consume_digit_run(|c| ('0'..='7').contains(&c));
+            if digits.is_empty() {
+                return Err(LexErrorKind::InvalidNum);
+            }
+            let value = u64::from_str_radix(&digits, 8).map_err(|_| LexErrorKind::InvalidNum)?;
+            let ty = self.consume_suffix(INT_SUFFIXES)?.unwrap_or(Type::UInt64);
+            return Ok(Token::Num(NumTok { value: NumValue::Int(value), ty }));
+        }
+        if self.peek() == Some('0') && matches!(self.peek_at(1), Some('b') | Some('B')) {
+            self.advance();
+            self.advance();
+            let digits = self.consume_digit_run(|c| c == '0' || c == '1');
+            if digits.is_empty() {
+                return Err(LexErrorKind::InvalidNum);
+            }
+            let value = u64::from_str_radix(&digits, 2).map_err(|_| LexErrorKind::InvalidNum)?;
+            let ty = self.consume_suffix(INT_SUFFIXES)?.unwrap_or(Type::UInt64);
+            return Ok(Token::Num(NumTok { value: NumValue::Int(value), ty }));
+        }
+
+        let int_part = self.consume_digit_run(|c| c.is_ascii_digit());
+        let mut frac_part = None;
+        if self.peek() == Some('.') && matches!(self.peek_at(1), Some(c) if c.is_ascii_digit()) {
+            self.advance();
+            frac_part = Some(self.consume_digit_run(|c| c.is_ascii_digit()));
+        }
+
+        match frac_part {
+            Some(frac) => {
+                let text = format!("{}.{}", int_part, frac);
+                let value = text.parse::<f64>().map_err(|_| LexErrorKind::InvalidNum)?;
+                let ty = self.consume_suffix(FLOAT_SUFFIXES)?.unwrap_or(Type::Float);
+                Ok(Token::Num(NumTok { value: NumValue::Float(value), ty }))
+            },
+            None => {
+                let value = int_part.parse::<u64>().map_err(|_| LexErrorKind::InvalidNum)?;
+                let ty = self.consume_suffix(INT_SUFFIXES)?.unwrap_or(Type::UInt64);
+                Ok(Token::Num(NumTok { value: NumValue::Int(value), ty }))
+            },
+        }
+    }
+
+    // Parses the body of a `0x...` literal: a hex integer, or, when a `p`/`P`
+    // binary exponent follows the digits (with or without a `.` fraction), a hex
+    // float like `0x1.8p3`. A `.` with no exponent is rejected.
+    fn scan_hex_number(
+        &mut self, int_suffixes: &[(&str, Type)], float_suffixes: &[(&str, Type)],
+    ) -> Result<Token, LexErrorKind> {
+        let is_hex_digit = |c: char| c.is_ascii_hexdigit();
+
+        let int_digits = self.consume_digit_run(is_hex_digit);
+        let mut has_dot = false;
+        let mut frac_digits = String::new();
+        if self.peek() == Some('.') {
+            has_dot = true;
+            self.advance();
+            frac_digits = self.consume_digit_run(is_hex_digit);
+        }
+
+        if int_digits.is_empty() && frac_digits.is_empty() {
+            return Err(LexErrorKind::InvalidNum);
+        }
+
+        if matches!(self.peek(), Some('p') | Some('P')) {
+            self.advance();
+            let sign = match self.peek() {
+                Some('+') => {
+                    self.advance();
+                    1i64
+                },
+                Some('-') => {
+                    self.advance();
+                    -1i64
+                },
+                _ => 1i64,
+            };
+            let exp_digits = self.consume_digit_run(|c| c.is_ascii_digit());
+            if exp_digits.is_empty() {
+                return Err(LexErrorKind::InvalidNum);
+            }
+            let exponent = sign * exp_digits.parse::<i64>().map_err(|_| LexErrorKind::InvalidNum)?;
+
+            let mantissa_text = format!("{}{}", int_digits, frac_digits);
+            let mantissa = if mantissa_text.is_empty() {
+                0
+            } else {
+                u64::from_str_radix(&mantissa_text, 16).map_err(|_| LexErrorKind::InvalidNum)?
+            };
+            let scale = exponent - 4 * frac_digits.len() as i64;
+            let value = mantissa as f64 * 2f64.powi(scale as i32);
+
+            let ty = self.consume_suffix(float_suffixes)?.unwrap_or(Type::Float);
+            return Ok(Token::Num(NumTok { value: NumValue::Float(value), ty }));
+        }
+
+        if has_dot {
+            // A `.` with no `p`/`P` exponent isn't a valid hex float.
+            return Err(LexErrorKind::InvalidNum);
+        }
+
+        let value = u64::from_str_radix(&int_digits, 16).map_err(|_| LexErrorKind::InvalidNum)?;
+        let ty = self.consume_suffix(int_suffixes)?.unwrap_or(Type::UInt64);
+        Ok(Token::Num(NumTok { value: NumValue::Int(value), ty }))
+    }
+
+    fn scan_ident(&mut self) -> Token {
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_alphanumeric() || c == '_') {
+            self.advance();
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        match text.as_str() {
+            "extern" => Token::Extern,
+            "fn" => Token::Fn,
+            "let" => Token::Let,
+            "if" => Token::If,
+            "else" => Token::Else,
+            _ => Token::Ident(text),
+        }
+    }
+}
+
+impl Iterator for Lexer {
+    type Item = Result<Spanned<Token>, LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.skip_trivia();
+        self.peek()?;
+        let start = self.mark();
+
+        let result = if self.peek().unwrap().is_ascii_digit() {
+            self.scan_number()
+        } else if matches!(self.peek(), Some(c) if c.is_alphabetic() || c == '_') {
+            Ok(self.scan_ident())
+        } else {
+            let c = self.advance().unwrap();
+            match c {
+                '(' => Ok(Token::OpenParen),
+                ')' => Ok(Token::CloseParen),
+                '{' => Ok(Token::OpenBrace),
+                '}' => Ok(Token::CloseBrace),
+                ',' => Ok(Token::Comma),
+                '=' => Ok(Token::Assign),
+                '+' | '-' | '*' | '/' | '>' | '<' | '!' | '&' | '|' | '^' => Ok(Token::Op(c)),
+                _ => Err(LexErrorKind::UnexpectedChar(c)),
+            }
+        };
+
+        let span = self.span_from(start);
+        Some(match result {
+            Ok(node) => Ok(Spanned { node, span }),
+            Err(kind) => Err(LexError { kind, span }),
+        })
+    }
+}
+
+/// A previously-lexed source buffer, kept around so a single edit can be re-lexed
+/// incrementally instead of re-scanning the whole file on every keystroke — the
+/// same capability a tree-sitter-style grammar provides to an editor/LSP front end.
+/// Spans are byte/line/col positions into `source`; a future diagnostics pass that
+/// wants these reported from `LexError`/parser errors still needs the live
+/// pipeline lexing through this module instead of the `lex` crate (see the note
+/// on `crate::lexer` in `lib.rs`).
+pub struct LexedBuffer {
+    pub source: String,
+    pub tokens: Vec<Spanned<Token>>,
+}
+
+impl LexedBuffer {
+    pub fn new(source: &str) -> Result<Self, LexError> {
+        let tokens = Lexer::new(source).collect::<Result<Vec<_>, _>>()?;
+        Ok(LexedBuffer { source: source.to_owned(), tokens })
+    }
+
+    /// Splices `new_text` into the source in place of `edit_range` (byte offsets
+    /// into the *current* source), then re-lexes only the lines the edit touched:
+    /// tokens on lines wholly before the edit are kept as-is, and tokens on lines
+    /// wholly after it are reused too, just translated by how much the edit
+    /// shifted byte offsets and line numbers — their text and their column within
+    /// their line are unchanged, so there's nothing to re-scan. The edited lines
+    /// are re-lexed whole, since a `//` comment or a token next to the edit can
+    /// change with it, so a small edit anywhere in a large file — not just near
+    /// the end — costs roughly the size of the lines it touches, not a re-lex of
+    /// the whole remainder.
+    pub fn relex_edit(&mut self, edit_range: Range<usize>, new_text: &str) -> Result<(), LexError> {
+        let byte_delta = new_text.len() as i64 - (edit_range.end - edit_range.start) as i64;
+        let line_delta = new_text.matches('\n').count() as i64
+            - self.source[edit_range.clone()].matches('\n').count() as i64;
+        // Both in the pre-edit source: the (1-indexed) line the edit ends on, and
+        // the byte offset where the line the edit starts on begins.
+        let edit_end_line = self.source[..edit_range.end].matches('\n').count() as u32 + 1;
+        let line_start = self.source[..edit_range.start].rfind('\n').map_or(0, |i| i + 1);
+
+        self.source.replace_range(edit_range.clone(), new_text);
+
+        let keep = self.tokens.iter().take_while(|t| t.span.end <= line_start).count();
+        let rescan_from = line_start;
+
+        // The first token untouched by the edit: the first one on a line after the
+        // (pre-edit) line the edit ends on, so everything from its line's start on
+        // is identical post-edit, just shifted by `byte_delta`/`line_delta`. Falls
+        // back to "nothing is untouched" (re-lex to EOF) when no token follows the
+        // edit's last line.
+        let stale_end = keep + self.tokens[keep..].iter().take_while(|t| t.span.line <= edit_end_line).count();
+        let rescan_to = self
+            .tokens
+            .get(stale_end)
+            .map_or(self.source.len(), |t| (t.span.start as i64 + byte_delta) as usize);
+
+        let prefix = &self.source[..rescan_from];
+        let line_offset = prefix.matches('\n').count() as u32;
+        // Column of `rescan_from` itself within its (original) line.
+        let rescan_from_col = prefix.rsplit('\n').next().unwrap_or("").chars().count() as u32 + 1;
+
+        let mut fresh = Lexer::new(&self.source[rescan_from..rescan_to]).collect::<Result<Vec<_>, _>>()?;
+        for t in &mut fresh {
+            t.span.start += rescan_from;
+            t.span.end += rescan_from;
+            if t.span.line == 1 {
+                t.span.col = rescan_from_col + (t.span.col - 1);
+            }
+            t.span.line += line_offset;
+        }
+
+        let mut reused = self.tokens.split_off(stale_end);
+        for t in &mut reused {
+            t.span.start = (t.span.start as i64 + byte_delta) as usize;
+            t.span.end = (t.span.end as i64 + byte_delta) as usize;
+            t.span.line = (t.span.line as i64 + line_delta) as u32;
+        }
+
+        self.tokens.truncate(keep);
+        self.tokens.extend(fresh);
+        self.tokens.append(&mut reused);
+        Ok(())
+    }
+}
@@ -0,0 +1,6 @@
+// `lexer` is the span-tracking, `Type`-inferring scanner exercised by
+// `tests/lexer.rs`. The live `lightc` pipeline (see `main.rs`/`test_runner.rs`)
+// still lexes through the separate `lex` crate; wiring this module in as its
+// replacement is out of reach from this tree (`lex`/`parse` aren't part of it),
+// so it stays staged here until that migration lands upstream.
+pub mod lexer;
@@ -1,89 +1,277 @@
-use clap::Parser as Clap;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::{env, fs, process};
 
+use clap::Parser as Clap;
+
+use ast::{Ast, AstNode};
+use cache::Cache;
 use codegen::Codegen;
-use common::{CliArgs, SymbolTable};
+use common::{CliArgs, EmitKind, SymbolTable};
+use interp::Interp;
 use lex::Lex;
 use lower::Lower;
 use parse::Parse;
 use tych::Tych;
 
+mod cache;
+mod test_runner;
+
 fn main() {
     let args = CliArgs::parse();
-    let source = fs::read_to_string(args.file.as_path()).expect("Error opening file");
-    let module_name = get_module_name(&args.file);
+
+    if let Some(dir) = &args.test {
+        process::exit(if test_runner::run(dir, &args) { 0 } else { 1 });
+    }
+
+    let stdlib = resolve_stdlib(&args);
+    if !stdlib.exists() {
+        eprintln!(
+            "Error: runtime object `{}` doesn't exist (pass --stdlib to point at the one for `{}`)",
+            stdlib.display(),
+            args.target.as_deref().unwrap_or("the host target"),
+        );
+        process::exit(1);
+    }
+
     let (root_dir, build_dir) = setup_build_env().expect("Error setting up build environment");
+
+    // Fast path: a single-file build whose object is already newer than its
+    // source and `stdlib.o` needs nothing from the frontend at all. Multi-file
+    // builds always re-run it below (one file's typecheck can depend on symbols
+    // another declares), and so does `--force` or any stage-dump flag, so
+    // diagnostics are never silently suppressed by a stale-check false positive.
+    // Also bails when `--emit` asks for `.ll`/`.s` artifacts this path wouldn't
+    // regenerate: the object alone being fresh doesn't mean those are.
+    let dump_requested = args.show_tokens || args.show_ast || args.show_typed_ast || args.show_hir;
+    if !args.force && !dump_requested && !args.run && args.emit_ir.is_none() {
+        if let [file] = args.files.as_slice() {
+            let module_name = get_module_name(file);
+            let object = build_dir.join(&module_name).with_extension("o");
+            let extra_artifacts_up_to_date = emitted_artifacts(&args.emit, &root_dir, &module_name)
+                .iter()
+                .all(|artifact| is_up_to_date(artifact, &[file.as_path()]));
+            if extra_artifacts_up_to_date && is_up_to_date(&object, &[file.as_path(), stdlib.as_path()]) {
+                link(args, root_dir, vec![object], stdlib);
+                return;
+            }
+        }
+    }
+
     let mut symbol_table = SymbolTable::new();
 
-    // Lexer
-    let tokens = Lex::new(&source).scan().unwrap_or_else(|e| {
-        eprintln!("Lexing error: {}", e);
+    // Every file gets its own module name, but they all typecheck against one
+    // `symbol_table` so a function/struct declared in one file is visible from
+    // the others. Reject up front if two files would collide rather than
+    // silently letting the second shadow the first in the object it produces.
+    let mut module_names = HashMap::with_capacity(args.files.len());
+    for file in &args.files {
+        let module_name = get_module_name(file);
+        if let Some(earlier) = module_names.insert(module_name.clone(), file.clone()) {
+            eprintln!(
+                "Error: `{}` and `{}` both resolve to module name `{}`",
+                earlier.display(),
+                file.display(),
+                module_name
+            );
+            process::exit(1);
+        }
+    }
+
+    // The interpreter never produces an object file, so there's nothing for the
+    // cache to key or store.
+    let cache = (!args.run).then(|| Cache::open(&args)).flatten();
+
+    // A single-file cache hit needs nothing from the frontend either: copy the
+    // cached object into the build dir and go straight to the link step.
+    if let (Some(cache), [file]) = (&cache, args.files.as_slice()) {
+        let needs_codegen_artifacts = args.emit.iter().any(|kind| matches!(kind, EmitKind::LlvmIr | EmitKind::Asm));
+        if !dump_requested && args.emit_ir.is_none() && !needs_codegen_artifacts {
+            let source = fs::read_to_string(file).expect("Error opening file");
+            if let Some(cached) = cache.get(&Cache::digest(&source, &args, &stdlib)) {
+                let module_file = build_dir.join(get_module_name(file)).with_extension("o");
+                fs::copy(cached, &module_file).expect("Error copying cached object");
+                link(args, root_dir, vec![module_file], stdlib);
+                return;
+            }
+        }
+    }
+
+    let mut typed_asts = Vec::with_capacity(args.files.len());
+    for file in &args.files {
+        let module_name = get_module_name(file);
+        let source = fs::read_to_string(file).expect("Error opening file");
+        let typed_ast = frontend(file, &source, &mut symbol_table, &args);
+        typed_asts.push((module_name, source, typed_ast));
+    }
+
+    // Dump the post-typecheck IR and stop, rather than continuing on to codegen.
+    // Lets callers inspect or hand-edit the exact types and symbols the frontend
+    // produced, and re-ingest it later via `ir::parse()`.
+    if let Some(path) = &args.emit_ir {
+        let merged = merge(typed_asts.iter().map(|(_, _, ast)| ast.clone()));
+        let text = ir::dump(&merged, &symbol_table);
+        fs::write(path, text).unwrap_or_else(|e| {
+            eprintln!("Error writing IR to `{}`: {}", path.display(), e);
+            process::exit(1);
+        });
+        process::exit(0);
+    }
+
+    // Skip the native backend entirely and execute the program directly when
+    // the user just wants a fast edit-run loop.
+    if args.run {
+        let merged = merge(typed_asts.into_iter().map(|(_, _, ast)| ast));
+        let result = Interp::new(&symbol_table).run(merged).unwrap_or_else(|e| {
+            eprintln!("Runtime error: {}", e);
+            process::exit(1);
+        });
+        let _ = result;
+        process::exit(0);
+    }
+
+    // One object per input file, in the order the files were given, so the final
+    // link command line is deterministic. The frontend above always ran (other
+    // files' typechecking may depend on symbols it inserted), so a cache hit here
+    // only buys back Lower+Codegen rather than the whole pipeline like it does for
+    // a single-file build.
+    // `Codegen::run` is the only thing that writes `.ll`/`.s`, as a side effect of
+    // running; a cache hit skips it entirely, so trusting a cached object when
+    // either of those was asked for would silently omit them on a second
+    // invocation. Treat the cache as a miss in that case rather than teaching it
+    // to store and restore artifacts it was never designed to track.
+    let needs_codegen_artifacts = args.emit.iter().any(|kind| matches!(kind, EmitKind::LlvmIr | EmitKind::Asm));
+
+    let mut objects = Vec::with_capacity(typed_asts.len());
+    for (module_name, source, typed_ast) in typed_asts {
+        let digest = cache.as_ref().map(|_| Cache::digest(&source, &args, &stdlib));
+
+        let cached = if needs_codegen_artifacts {
+            None
+        } else {
+            cache.as_ref().zip(digest.as_deref()).and_then(|(c, d)| c.get(d))
+        };
+
+        let module_file = if let Some(cached) = cached {
+            let module_file = build_dir.join(&module_name).with_extension("o");
+            fs::copy(cached, &module_file).expect("Error copying cached object");
+            module_file
+        } else {
+            let hir = Lower::new(&mut symbol_table).walk(typed_ast).unwrap_or_else(|e| {
+                eprintln!("Lowering error: {}", e);
+                process::exit(1);
+            });
+
+            if args.show_hir {
+                println!("HIR ({}):", module_name);
+                for node in hir.nodes() {
+                    println!("{}", node);
+                }
+                println!();
+            }
+
+            // `args.target` rides along in `&args`; `Codegen` configures its LLVM
+            // target machine from it, falling back to the host triple when unset.
+            let output = Codegen::run(hir, &module_name, symbol_table.clone(), build_dir.clone(), &args, false)
+                .unwrap_or_else(|e| panic!("Error compiling `{}`: {}", module_name, e));
+
+            // `--emit=llvm-ir`/`--emit=asm` ask `Codegen` (already handed `&args`) to
+            // write `<module>.ll`/`.s` alongside the object; copy whichever of those
+            // it produced up to `root_dir` so they're visible without digging in
+            // `.build`, same as `--compile-only` already does for the object.
+            for artifact in [output.ir_file_path(), output.asm_file_path()].into_iter().flatten() {
+                let dest = root_dir.join(artifact.file_name().expect("Error getting artifact filename"));
+                fs::copy(&artifact, dest).expect("Error copying emitted artifact");
+            }
+
+            let module_file = output.as_file_path();
+
+            if let Some(cache) = &cache {
+                if let Err(e) = cache.put(digest.as_deref().unwrap(), &module_file) {
+                    eprintln!("Warning: couldn't populate compile cache for `{}`: {}", module_name, e);
+                }
+            }
+
+            module_file
+        };
+
+        objects.push(module_file);
+    }
+
+    link(args, root_dir, objects, stdlib);
+}
+
+// Lexes, parses and typechecks a single file against the shared `symbol_table`,
+// running any of the `--show-*` debug dumps the caller asked for along the way.
+fn frontend(file: &Path, source: &str, symbol_table: &mut SymbolTable, args: &CliArgs) -> Ast<AstNode> {
+    let tokens = Lex::new(source).scan().unwrap_or_else(|e| {
+        eprintln!("Lexing error in `{}`: {}", file.display(), e);
         process::exit(1);
     });
 
     if args.show_tokens {
-        println!("Tokens:");
+        println!("Tokens ({}):", file.display());
         tokens.iter().for_each(|t| println!("{:?}", t));
         println!();
     }
 
-    // Parser
-    let parser = Parse::new(&tokens, &mut symbol_table);
+    let parser = Parse::new(&tokens, symbol_table);
     let ast = parser.parse().unwrap_or_else(|e| {
-        eprintln!("Parsing error: {}", e);
+        eprintln!("Parsing error in `{}`: {}", file.display(), e);
         process::exit(1);
     });
 
     if args.show_ast {
-        println!("AST:");
+        println!("AST ({}):", file.display());
         for node in ast.nodes() {
             println!("{}", node);
         }
         println!();
     }
 
-    // Type checker
-    let typed_ast = Tych::new(&mut symbol_table).walk(ast).unwrap_or_else(|e| {
-        eprintln!("Type checking error: {}", e);
+    let typed_ast = Tych::new(symbol_table).walk(ast).unwrap_or_else(|e| {
+        eprintln!("Type checking error in `{}`: {}", file.display(), e);
         process::exit(1);
     });
 
     if args.show_typed_ast {
-        println!("Typed AST:");
+        println!("Typed AST ({}):", file.display());
         for node in typed_ast.nodes() {
             println!("{}", node);
         }
         println!();
     }
 
-    // Lower
-    let hir = Lower::new(&mut symbol_table).walk(typed_ast).unwrap_or_else(|e| {
-        eprintln!("Lowering error: {}", e);
-        process::exit(1);
-    });
+    typed_ast
+}
 
-    if args.show_hir {
-        println!("HIR:");
-        for node in hir.nodes() {
-            println!("{}", node);
+// Concatenates several files' typed ASTs into the single `Ast` that `ir::dump()`
+// and `Interp::run()` expect, preserving file order.
+fn merge(asts: impl Iterator<Item = Ast<AstNode>>) -> Ast<AstNode> {
+    let mut merged = Ast::new();
+    for ast in asts {
+        for node in ast.into_nodes() {
+            merged.add(node);
         }
-        println!();
     }
+    merged
+}
 
-    // Codegen
-    let module_file = Codegen::run(hir, &module_name, symbol_table, build_dir, &args, false)
-        .unwrap_or_else(|e| panic!("Error compiling `{}`: {}", args.file.display(), e))
-        .as_file_path();
-
-    // If we just want the object file, copy it up to the root and exit
-    if args.compile_only {
-        let mut obj_file = root_dir;
-        obj_file.push(&module_name);
-        let obj_file = obj_file.as_path().with_extension("o");
-
-        fs::copy(module_file, obj_file).expect("Error copying object file");
+// Shared by both the cache-hit path (which skips straight here with a copied-in
+// object) and a normal run fresh off `Codegen::run()`. Copies the objects up to
+// `root_dir` when `--compile-only` or `--emit=obj` asks for them, and
+// short-circuits the clang step entirely unless `link` was requested (the
+// default), since `--compile-only` or `--emit=obj` alone mean the user just
+// wants the object files.
+fn link(args: CliArgs, root_dir: PathBuf, objects: Vec<PathBuf>, stdlib: PathBuf) {
+    if args.compile_only || args.emit.contains(&EmitKind::Obj) {
+        for object in &objects {
+            let dest = root_dir.join(object.file_name().expect("Error getting object filename"));
+            fs::copy(object, dest).expect("Error copying object file");
+        }
+    }
+    if args.compile_only || !args.emit.contains(&EmitKind::Link) {
         process::exit(0);
     }
 
@@ -92,16 +280,56 @@ fn main() {
         None => String::from("a.out"),
     };
 
-    Command::new("clang")
+    let inputs: Vec<&Path> =
+        objects.iter().chain(&args.link).map(PathBuf::as_path).chain([stdlib.as_path()]).collect();
+    if !args.force && is_up_to_date(Path::new(&outfile), &inputs) {
+        process::exit(0);
+    }
+
+    Command::new(&args.linker)
         .arg("-o")
         .arg(outfile)
-        .arg(module_file)
-        .arg("stdlib/stdlib.o")
+        .args(&objects)
+        .args(&args.link)
+        .arg(&stdlib)
         .arg("-lm")
         .spawn()
-        .expect("Error compiling")
+        .unwrap_or_else(|e| panic!("Error running linker `{}`: {}", args.linker, e))
         .wait()
-        .expect("Error waiting on clang");
+        .expect("Error waiting on linker");
+}
+
+// Resolves the runtime object to link against: the user's `--stdlib` when
+// given, otherwise `stdlib/stdlib.o` relative to the current directory (the
+// same repo-root-relative default the driver has always assumed). Also used by
+// `test_runner` so `--test --target ... --stdlib ...` builds the golden-file
+// suite against the same runtime a normal build would.
+pub(crate) fn resolve_stdlib(args: &CliArgs) -> PathBuf {
+    args.stdlib.clone().unwrap_or_else(|| PathBuf::from("stdlib/stdlib.o"))
+}
+
+// `output` counts as up to date if it exists and its mtime is at or after every
+// input's. A missing timestamp (either side) is treated as stale rather than
+// panicking, the same "don't trust it, rebuild" call `cache.rs` makes for a
+// missing `stdlib.o`.
+fn is_up_to_date(output: &Path, inputs: &[&Path]) -> bool {
+    let Ok(output_time) = fs::metadata(output).and_then(|m| m.modified()) else { return false };
+    inputs.iter().all(|input| fs::metadata(input).and_then(|m| m.modified()).is_ok_and(|t| t <= output_time))
+}
+
+// The `root_dir` paths the fast path must also find fresh before skipping the
+// frontend: `.ll`/`.s` land there only when asked for via `--emit`, same as the
+// object does via `--compile-only`/`--emit=obj`. `Link` and `Obj` aren't listed
+// here — the fast path already tracks the object itself, and `link()` always
+// re-links/re-copies on its own up-to-date check.
+fn emitted_artifacts(emit: &[EmitKind], root_dir: &Path, module_name: &str) -> Vec<PathBuf> {
+    emit.iter()
+        .filter_map(|kind| match kind {
+            EmitKind::LlvmIr => Some(root_dir.join(module_name).with_extension("ll")),
+            EmitKind::Asm => Some(root_dir.join(module_name).with_extension("s")),
+            EmitKind::Obj | EmitKind::Link => None,
+        })
+        .collect()
 }
 
 fn get_module_name(path: &Path) -> String {
@@ -113,15 +341,15 @@ fn get_module_name(path: &Path) -> String {
         .to_owned()
 }
 
+// Used to unconditionally wipe and recreate `.build` on every run; now it's kept
+// across runs so `is_up_to_date()` has something to compare mtimes against, and
+// a stale object is simply overwritten in place when it's rebuilt.
 fn setup_build_env() -> std::io::Result<(PathBuf, PathBuf)> {
     let root_dir = env::current_dir()?;
     let mut build_dir = root_dir.clone();
     build_dir.push(".build");
 
-    if build_dir.exists() {
-        fs::remove_dir_all(&build_dir)?;
-    }
-    fs::create_dir(&build_dir)?;
+    fs::create_dir_all(&build_dir)?;
 
     Ok((root_dir, build_dir))
 }
@@ -0,0 +1,275 @@
+use std::io::Read as _;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+use std::{fs, thread};
+
+use common::{CliArgs, SymbolTable};
+use lex::Lex;
+use lower::Lower;
+use parse::Parse;
+use tych::Tych;
+
+use codegen::Codegen;
+
+/// Bounds how long a compiled test's `a.out` is allowed to run before the runner
+/// kills it and counts the test as a failure; a looping `RUN-PASS` test can't hang
+/// the whole suite.
+const RUN_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Lex,
+    Parse,
+    Tych,
+    Lower,
+    Codegen,
+}
+
+impl Stage {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "Lex" => Some(Stage::Lex),
+            "Parse" => Some(Stage::Parse),
+            "Tych" => Some(Stage::Tych),
+            "Lower" => Some(Stage::Lower),
+            "Codegen" => Some(Stage::Codegen),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    CompileFail,
+    RunPass,
+}
+
+// The expectations a test's `//~` comments encode. `outcome` defaults to
+// `RunPass` when a test has no directives at all, per the compiletest-style
+// convention this mirrors.
+#[derive(Debug, Default)]
+struct Directives {
+    outcome: Option<Outcome>,
+    error: Option<(Stage, String)>,
+    stdout: Vec<String>,
+}
+
+fn parse_directives(source: &str) -> Directives {
+    let mut directives = Directives::default();
+    for line in source.lines() {
+        let Some(rest) = line.trim_start().strip_prefix("//~") else { continue };
+        let rest = rest.trim();
+
+        if rest == "COMPILE-FAIL" {
+            directives.outcome = Some(Outcome::CompileFail);
+        } else if rest == "RUN-PASS" {
+            directives.outcome = Some(Outcome::RunPass);
+        } else if let Some(rest) = rest.strip_prefix("ERROR ") {
+            let (stage, substring) = rest.split_once(' ').unwrap_or((rest, ""));
+            match Stage::parse(stage) {
+                Some(stage) => directives.error = Some((stage, substring.to_owned())),
+                None => eprintln!("Warning: unknown stage `{}` in `//~ ERROR` directive", stage),
+            }
+        } else if let Some(rest) = rest.strip_prefix("STDOUT ") {
+            directives.stdout.push(rest.to_owned());
+        }
+    }
+    directives
+}
+
+struct TestResult {
+    path: PathBuf,
+    failure: Option<String>,
+}
+
+/// Walks `dir` for `.lt` files, compiles and runs each one in an isolated temp
+/// build dir, checks the outcome against its `//~` directives, and prints a
+/// pass/fail summary. Returns `true` iff every test passed. Links against the
+/// same `--linker`/`--stdlib`/`--target` the caller passed to `lightc --test`,
+/// so the suite can validate a cross-compiled or custom-runtime build instead
+/// of always linking against the host's `stdlib/stdlib.o` with `clang`.
+pub fn run(dir: &Path, args: &CliArgs) -> bool {
+    let mut tests = vec![];
+    collect_lt_files(dir, &mut tests);
+    tests.sort();
+
+    let results: Vec<TestResult> = tests.iter().map(|path| run_one(path, args)).collect();
+
+    let failed: Vec<_> = results.iter().filter(|r| r.failure.is_some()).collect();
+    for result in &failed {
+        println!("FAIL {}", result.path.display());
+        println!("  {}", result.failure.as_deref().unwrap());
+    }
+    println!("{}/{} tests passed", results.len() - failed.len(), results.len());
+
+    failed.is_empty()
+}
+
+fn collect_lt_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_lt_files(&path, out);
+        } else if path.extension().is_some_and(|ext| ext == "lt") {
+            out.push(path);
+        }
+    }
+}
+
+fn run_one(path: &Path, args: &CliArgs) -> TestResult {
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => return TestResult { path: path.to_owned(), failure: Some(format!("couldn't read file: {}", e)) },
+    };
+    let directives = parse_directives(&source);
+    let expected_outcome = directives.outcome.unwrap_or(Outcome::RunPass);
+
+    let build_dir = std::env::temp_dir().join(format!("lightc-test-{}", std::process::id())).join(
+        path.file_stem().and_then(|s| s.to_str()).unwrap_or("test"),
+    );
+    let _ = fs::remove_dir_all(&build_dir);
+    if let Err(e) = fs::create_dir_all(&build_dir) {
+        return TestResult { path: path.to_owned(), failure: Some(format!("couldn't create build dir: {}", e)) };
+    }
+
+    let failure = compile_and_run(path, &source, &build_dir, &directives, expected_outcome, args);
+    TestResult { path: path.to_owned(), failure }
+}
+
+// Runs Lex->Parse->Tych->Lower->Codegen, stopping (and reporting) at the first
+// stage that errors, then compares against `directives`/`expected_outcome`.
+// Capturing each stage's `Result` instead of calling `process::exit` (as `main()`
+// does) is what lets the runner keep going across hundreds of tests.
+fn compile_and_run(
+    path: &Path, source: &str, build_dir: &Path, directives: &Directives, expected_outcome: Outcome, args: &CliArgs,
+) -> Option<String> {
+    let mut symbol_table = SymbolTable::new();
+
+    macro_rules! stage_err {
+        ($stage:expr, $err:expr) => {{
+            return check_error($stage, &$err.to_string(), directives, expected_outcome);
+        }};
+    }
+
+    let tokens = match Lex::new(source).scan() {
+        Ok(tokens) => tokens,
+        Err(e) => stage_err!(Stage::Lex, e),
+    };
+
+    let ast = match Parse::new(&tokens, &mut symbol_table).parse() {
+        Ok(ast) => ast,
+        Err(e) => stage_err!(Stage::Parse, e),
+    };
+
+    let typed_ast = match Tych::new(&mut symbol_table).walk(ast) {
+        Ok(typed_ast) => typed_ast,
+        Err(e) => stage_err!(Stage::Tych, e),
+    };
+
+    let module_name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("test").to_owned();
+    let hir = match Lower::new(&mut symbol_table).walk(typed_ast.clone()) {
+        Ok(hir) => hir,
+        Err(e) => stage_err!(Stage::Lower, e),
+    };
+
+    let module_file = match Codegen::run(
+        hir,
+        &module_name,
+        symbol_table.clone(),
+        build_dir.to_owned(),
+        &default_args(args.target.clone()),
+        false,
+    ) {
+        Ok(output) => output.as_file_path(),
+        Err(e) => stage_err!(Stage::Codegen, e),
+    };
+
+    // Lower and Codegen (which `//~ ERROR Lower ...`/`//~ ERROR Codegen ...` can
+    // target, same as any other stage) have both now run, so this is the first
+    // point a `COMPILE-FAIL` test can be judged to have wrongly passed the whole
+    // pipeline.
+    if expected_outcome == Outcome::CompileFail {
+        return Some("expected a compile error but the frontend accepted the program".to_owned());
+    }
+
+    let exe = build_dir.join("a.out");
+    let link_status = Command::new(&args.linker)
+        .arg("-o")
+        .arg(&exe)
+        .arg(module_file)
+        .arg(crate::resolve_stdlib(args))
+        .arg("-lm")
+        .status();
+    if let Err(e) = link_status {
+        return Some(format!("linking failed: {}", e));
+    }
+
+    match run_with_timeout(&exe, RUN_TIMEOUT) {
+        Ok(stdout) => {
+            let expected = directives.stdout.join("\n");
+            if !expected.is_empty() && stdout.trim_end() != expected.trim_end() {
+                return Some(format!("STDOUT mismatch:\n  expected: {:?}\n  actual:   {:?}", expected, stdout));
+            }
+            None
+        },
+        Err(e) => Some(format!("running `a.out` failed: {}", e)),
+    }
+}
+
+fn check_error(stage: Stage, message: &str, directives: &Directives, expected_outcome: Outcome) -> Option<String> {
+    if expected_outcome != Outcome::CompileFail {
+        return Some(format!("unexpected {:?} error: {}", stage, message));
+    }
+    match &directives.error {
+        Some((expected_stage, substring)) => {
+            if *expected_stage != stage {
+                Some(format!("expected an error from {:?}, but {:?} failed first: {}", expected_stage, stage, message))
+            } else if !message.contains(substring.as_str()) {
+                Some(format!("{:?} error `{}` doesn't contain expected substring `{}`", stage, message, substring))
+            } else {
+                None
+            }
+        },
+        // COMPILE-FAIL with no `//~ ERROR` directive just asserts *some* stage failed.
+        None => None,
+    }
+}
+
+// Spawns `exe`, capturing stdout, and kills it if it outruns `timeout` rather
+// than letting a looping test hang the whole suite.
+fn run_with_timeout(exe: &Path, timeout: Duration) -> std::io::Result<String> {
+    let mut child = Command::new(exe).stdout(Stdio::piped()).spawn()?;
+    let mut stdout = child.stdout.take().expect("child was spawned with a piped stdout");
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buf = String::new();
+        let _ = stdout.read_to_string(&mut buf);
+        let _ = tx.send(buf);
+    });
+
+    match rx.recv_timeout(timeout) {
+        Ok(buf) => {
+            let _ = child.wait();
+            Ok(buf)
+        },
+        Err(_) => {
+            let _ = child.kill();
+            Err(std::io::Error::other(format!("test exceeded the {:?} timeout", timeout)))
+        },
+    }
+}
+
+// `Codegen::run()` wants a `&CliArgs` for its own `--emit`/target options; the
+// test runner always wants a plain native object, so it hands over the defaults
+// with `--target` folded in from whatever the caller passed `--test` alongside,
+// so a cross-compiled `--test` run builds against the right target machine.
+fn default_args(target: Option<String>) -> CliArgs {
+    use clap::Parser;
+    let mut args = CliArgs::parse_from(["lightc", "unused.lt"]);
+    args.target = target;
+    args
+}
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+mod symbol;
+
+pub use symbol::{AssocData, EnumData, FnData, StructData, Symbol, VarData, VariantData};
+
+/// Anything a `SymbolTable` can hold: needs a name to key itself by, and a way to
+/// say whether it would conflict with an already-present entry (most types never
+/// do; see `Symbol::conflicts_with()` for the one case that can).
+pub trait Symbolic {
+    fn name(&self) -> &str;
+
+    fn conflicts_with(&self, _other: &Self) -> bool {
+        false
+    }
+}
+
+/// A scoped symbol table: a stack of maps, innermost scope last. `get()` walks
+/// from the innermost scope outward so a local shadows an outer declaration of
+/// the same name; `insert()` always writes into the innermost scope.
+#[derive(Debug, Clone)]
+pub struct SymbolTable<T: Symbolic + Clone> {
+    scopes: Vec<HashMap<String, T>>,
+}
+
+impl<T: Symbolic + Clone> SymbolTable<T> {
+    pub fn new() -> Self {
+        SymbolTable { scopes: vec![HashMap::new()] }
+    }
+
+    pub fn enter_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    pub fn leave_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    pub fn get(&self, name: &str) -> Option<&T> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.scopes.iter().flat_map(|scope| scope.values())
+    }
+
+    /// Inserts `symbol` into the innermost scope. Rejects it with a diagnostic
+    /// instead of silently shadowing when it `conflicts_with()` anything already
+    /// visible in the table (across every scope, not just the innermost one),
+    /// e.g. an enum variant declared with the same name as an in-scope type.
+    pub fn insert(&mut self, symbol: T) -> Result<(), String> {
+        if let Some(existing) = self.iter().find(|existing| symbol.conflicts_with(existing)) {
+            return Err(format!("`{}` conflicts with an existing symbol `{}`", symbol.name(), existing.name()));
+        }
+        self.scopes.last_mut().expect("no active scope").insert(symbol.name().to_owned(), symbol);
+        Ok(())
+    }
+}
+
+impl<T: Symbolic + Clone> Default for SymbolTable<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,35 +1,55 @@
+use serde::{Deserialize, Serialize};
+
 use crate::Symbolic;
 use common::Type;
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct FnData {
     args: Vec<(String, String)>,
     ret_ty: String,
     is_extern: bool,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct VarData {
     pub ty: Type,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct StructData {
     pub fields: Vec<(String, String)>,
     pub methods: Option<Vec<String>>,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+// `variants` holds each variant's name alongside its (optional) payload field
+// types, in declaration order. A `None` payload means a unit variant.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct EnumData {
+    pub variants: Vec<(String, Option<Vec<String>>)>,
+}
+
+// Lives in the *value* namespace (unlike the enum itself, which is keyed by
+// `_type_<name>` in the type namespace) so a variant can be referenced like any
+// other value while its parent enum is referenced like any other type.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct VariantData {
+    pub enum_name: String,
+    pub payload_tys: Option<Vec<String>>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Symbol {
     pub name: String,
     pub data: AssocData,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum AssocData {
     Fn(FnData),
     Var(VarData),
     Struct(StructData),
+    Enum(EnumData),
+    Variant(VariantData),
     Type(),
 }
 
@@ -45,6 +65,59 @@ impl Symbol {
         Symbol { name: String::from("_type_") + name, data: AssocData::Type() }
     }
 
+    pub fn new_var(name: &str, ty: &Type) -> Self {
+        Symbol::from((name, ty))
+    }
+
+    // Inserted under `_type_<name>`, same as `new_ty()`, so the enum itself lives in
+    // the type namespace while its variants (see `new_variant()`) live in the value
+    // namespace.
+    pub fn new_enum(name: &str, variants: &[(String, Option<Vec<String>>)]) -> Self {
+        Symbol {
+            name: String::from("_type_") + name,
+            data: AssocData::Enum(EnumData { variants: variants.to_vec() }),
+        }
+    }
+
+    // Inserted under the bare variant name, i.e. the value namespace. `SymbolTable`
+    // must reject this if `name` clashes with an in-scope type rather than silently
+    // shadowing it; see `Symbol::conflicts_with()`.
+    pub fn new_variant(name: &str, enum_name: &str, payload_tys: Option<Vec<String>>) -> Self {
+        Symbol {
+            name: name.to_owned(),
+            data: AssocData::Variant(VariantData { enum_name: enum_name.to_owned(), payload_tys }),
+        }
+    }
+
+    pub fn is_enum(&self) -> bool {
+        matches!(self.data, AssocData::Enum(_))
+    }
+
+    pub fn variants(&self) -> &[(String, Option<Vec<String>>)] {
+        match &self.data {
+            AssocData::Enum(e) => &e.variants,
+            _ => unreachable!("expected symbol to be an enum"),
+        }
+    }
+
+    pub fn is_variant(&self) -> bool {
+        matches!(self.data, AssocData::Variant(_))
+    }
+
+    pub fn variant_enum_name(&self) -> &str {
+        match &self.data {
+            AssocData::Variant(v) => &v.enum_name,
+            _ => unreachable!("expected symbol to be an enum variant"),
+        }
+    }
+
+    pub fn variant_payload_tys(&self) -> Option<&[String]> {
+        match &self.data {
+            AssocData::Variant(v) => v.payload_tys.as_deref(),
+            _ => unreachable!("expected symbol to be an enum variant"),
+        }
+    }
+
     pub fn set_name(&mut self, name: &str) {
         self.name = name.to_owned();
     }
@@ -89,6 +162,23 @@ impl Symbolic for Symbol {
     fn name(&self) -> &str {
         &self.name
     }
+
+    // A variant's bare name lives in the value namespace and a type's name lives in
+    // the (separately-keyed, `_type_`-prefixed) type namespace, so they never
+    // collide in the underlying map. This checks the *semantic* clash the request
+    // cares about: inserting a variant whose name shadows an in-scope type name is
+    // almost certainly a mistake, so `SymbolTable::insert()` consults this before
+    // accepting a new variant symbol rather than silently letting it shadow.
+    fn conflicts_with(&self, other: &Symbol) -> bool {
+        match (&self.data, &other.data) {
+            (AssocData::Variant(_), AssocData::Type())
+            | (AssocData::Variant(_), AssocData::Enum(_))
+            | (AssocData::Variant(_), AssocData::Struct(_)) => {
+                self.name == other.name.trim_start_matches("_type_")
+            },
+            _ => false,
+        }
+    }
 }
 
 // For new variables
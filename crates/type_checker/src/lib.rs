@@ -115,6 +115,7 @@ impl<'a> TypeChecker<'a> {
                 Array(ty, len) => init_literal!(Array, *ty, *len),
                 Void => unreachable!("void type for variable initialization annotation"),
                 Comp(_) => todo!(),
+                Enum(_) => todo!(),
             })
         }
     }
@@ -131,7 +132,7 @@ impl<'a> AstVisitor for TypeChecker<'a> {
     fn visit_for(&mut self, stmt: ast::For<Self::Node>) -> Self::Result {
         // Insert starting variable
         self.symbol_table.enter_scope();
-        self.symbol_table.insert(Symbol::new_var(&stmt.start_name, &stmt.start_antn));
+        self.symbol_table.insert(Symbol::new_var(&stmt.start_name, &stmt.start_antn))?;
 
         let start_expr = self.check_var_init(
             &stmt.start_name,
@@ -173,7 +174,7 @@ impl<'a> AstVisitor for TypeChecker<'a> {
     }
 
     fn visit_let(&mut self, stmt: ast::Let<Self::Node>) -> Self::Result {
-        self.symbol_table.insert(Symbol::new_var(stmt.name.as_str(), &stmt.antn));
+        self.symbol_table.insert(Symbol::new_var(stmt.name.as_str(), &stmt.antn))?;
         let init_node = self.check_var_init(&stmt.name, stmt.init.as_deref(), &stmt.antn, "let statement")?;
         Ok(AstNode::new_let(stmt.name, stmt.antn, Some(init_node)))
     }
@@ -196,7 +197,7 @@ impl<'a> AstVisitor for TypeChecker<'a> {
 
         // Insert args into the local scope table
         for arg in proto.args() {
-            self.symbol_table.insert(Symbol::new_var(&arg.0, &arg.1));
+            self.symbol_table.insert(Symbol::new_var(&arg.0, &arg.1))?;
         }
 
         let body_node = self.check_node(*body, None)?;
@@ -276,6 +277,7 @@ impl<'a> AstVisitor for TypeChecker<'a> {
                     Type::Array(..) => return Err("Literal is an integer in an array context".to_string()),
                     Type::Void => return Err("Literal is an integer in a void context".to_string()),
                     Type::Comp(_) => return Err("Literal is an integer in a compound context".to_string()),
+                    Type::Enum(_) => return Err("Literal is an integer in an enum context".to_string()),
                 },
                 Float(v) => match hint {
                     Type::Float => convert_num!(v, Float, f32),
@@ -1,6 +1,21 @@
+use common::Type;
 use lightc::lexer::Token::*;
 use lightc::lexer::*;
 
+fn int(value: u64, ty: Type) -> Token {
+    Num(NumTok { value: NumValue::Int(value), ty })
+}
+
+fn float(value: f64, ty: Type) -> Token {
+    Num(NumTok { value: NumValue::Float(value), ty })
+}
+
+// Strips spans so most tests can assert on token shape alone; a handful of
+// dedicated tests below check `Span`/`LexError` directly.
+fn tokens(input: &str) -> Result<Vec<Token>, LexErrorKind> {
+    Lexer::new(input).map(|r| r.map(|s| s.node).map_err(|e| e.kind)).collect()
+}
+
 #[test]
 fn test_lexer_full() {
     let input = "\
@@ -42,9 +57,9 @@ fn main() {
         Ident("y".to_string()),
         CloseParen,
         Op('*'),
-        Int(4.0),
+        int(4, Type::UInt64),
         Op('/'),
-        Int(4.0),
+        int(4, Type::UInt64),
         Ident("a".to_string()),
         Op('>'),
         Ident("b".to_string()),
@@ -60,9 +75,9 @@ fn main() {
         Assign,
         Ident("arith".to_string()),
         OpenParen,
-        Int(36.0),
+        int(36, Type::UInt64),
         Comma,
-        Int(434.0),
+        int(434, Type::UInt64),
         CloseParen,
         Ident("printf".to_string()),
         OpenParen,
@@ -71,17 +86,13 @@ fn main() {
         CloseBrace,
     ];
 
-    let lexer = Lexer::new(input);
-    assert_eq!(lexer.collect::<Result<Vec<_>, _>>().unwrap(), &output);
+    assert_eq!(tokens(input).unwrap(), &output);
 }
 
 #[test]
 fn test_lexer_err_num() {
     let input = "let foo = 1b4";
-    assert_eq!(
-        Lexer::new(input).collect::<Result<Vec<_>, _>>(),
-        Err(LexError::InvalidNum)
-    );
+    assert_eq!(tokens(input), Err(LexErrorKind::InvalidNum));
 }
 
 #[test]
@@ -96,13 +107,10 @@ foo
         Let,
         Ident("foo".to_string()),
         Assign,
-        Int(14.0),
+        int(14, Type::UInt64),
         Ident("foo".to_string()),
     ];
-    assert_eq!(
-        Lexer::new(input).collect::<Result<Vec<_>, _>>().unwrap(),
-        &output
-    );
+    assert_eq!(tokens(input).unwrap(), &output);
 }
 
 #[test]
@@ -110,11 +118,8 @@ fn test_lexer_trailing_comment() {
     let input = "\
 let foo = 14
 // line2";
-    let output = [Let, Ident("foo".to_string()), Assign, Int(14.0)];
-    assert_eq!(
-        Lexer::new(input).collect::<Result<Vec<_>, _>>().unwrap(),
-        &output
-    );
+    let output = [Let, Ident("foo".to_string()), Assign, int(14, Type::UInt64)];
+    assert_eq!(tokens(input).unwrap(), &output);
 }
 
 #[test]
@@ -130,7 +135,7 @@ if x > 3 {
         If,
         Ident("x".to_string()),
         Op('>'),
-        Int(3.0),
+        int(3, Type::UInt64),
         OpenBrace,
         Ident("print".to_string()),
         OpenParen,
@@ -144,8 +149,86 @@ if x > 3 {
         CloseParen,
         CloseBrace,
     ];
-    assert_eq!(
-        Lexer::new(input).collect::<Result<Vec<_>, _>>().unwrap(),
-        &output
-    );
+    assert_eq!(tokens(input).unwrap(), &output);
+}
+
+#[test]
+fn test_lexer_integer_bases_and_separators() {
+    let input = "0xFF 0o17 0b1010 1_000_000";
+    let output = [
+        int(0xFF, Type::UInt64),
+        int(0o17, Type::UInt64),
+        int(0b1010, Type::UInt64),
+        int(1_000_000, Type::UInt64),
+    ];
+    assert_eq!(tokens(input).unwrap(), &output);
+}
+
+#[test]
+fn test_lexer_type_suffixes() {
+    let input = "42u8 100i64 3.0f 2.0d";
+    let output = [
+        int(42, Type::UInt8),
+        int(100, Type::Int64),
+        float(3.0, Type::Float),
+        float(2.0, Type::Double),
+    ];
+    assert_eq!(tokens(input).unwrap(), &output);
+}
+
+#[test]
+fn test_lexer_hex_float() {
+    let input = "0x1.8p3";
+    let output = [float(12.0, Type::Float)];
+    assert_eq!(tokens(input).unwrap(), &output);
+}
+
+#[test]
+fn test_lexer_hex_float_requires_exponent() {
+    let input = "0x1.8";
+    assert_eq!(tokens(input), Err(LexErrorKind::InvalidNum));
+}
+
+#[test]
+fn test_lexer_spans_track_line_and_col() {
+    let input = "let foo\n  bar";
+    let spanned: Vec<_> = Lexer::new(input).collect::<Result<Vec<_>, _>>().unwrap();
+
+    assert_eq!(spanned[0].span, Span { start: 0, end: 3, line: 1, col: 1 });
+    assert_eq!(spanned[1].span, Span { start: 4, end: 7, line: 1, col: 5 });
+    assert_eq!(spanned[2].span, Span { start: 10, end: 13, line: 2, col: 3 });
+}
+
+#[test]
+fn test_lexer_span_skips_past_comment() {
+    let input = "// comment\nfoo";
+    let spanned = Lexer::new(input).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(spanned[0].span, Span { start: 11, end: 14, line: 2, col: 1 });
+}
+
+#[test]
+fn test_lexer_invalid_num_span() {
+    let input = "let foo = 1b4";
+    let err = Lexer::new(input).collect::<Result<Vec<_>, _>>().unwrap_err();
+    assert_eq!(err.kind, LexErrorKind::InvalidNum);
+    assert_eq!(err.span, Span { start: 10, end: 13, line: 1, col: 11 });
+}
+
+#[test]
+fn test_lexer_relex_edit_reuses_suffix() {
+    let mut buf = LexedBuffer::new("let foo = 1\nlet bar = 2").unwrap();
+    let original_bar_span = buf.tokens[5].span;
+
+    // Edit only the first literal; the `let bar = 2` tail shouldn't need a
+    // full file re-lex to be correct, but a conservative re-lex still must
+    // agree with lexing the edited source from scratch.
+    buf.relex_edit(10..11, "42").unwrap();
+
+    let expected = LexedBuffer::new("let foo = 42\nlet bar = 2").unwrap();
+    assert_eq!(buf.tokens.len(), expected.tokens.len());
+    for (got, want) in buf.tokens.iter().zip(expected.tokens.iter()) {
+        assert_eq!(got.node, want.node);
+        assert_eq!(got.span, want.span);
+    }
+    assert_ne!(buf.tokens[5].span, original_bar_span);
 }